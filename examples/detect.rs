@@ -1,4 +1,4 @@
-use cv_detectors::{FASTDetector, ImgCoords, KeypointDetector};
+use cv_detectors::{Corner, FASTDetector, KeypointDetector};
 use image::open;
 use std::env;
 use std::process::Command;
@@ -28,11 +28,11 @@ pub fn main() {
     let mut image_rgb = image.into_rgb();
 
     // detect features - nonmax
-    let mut features_nonmax = Vec::<ImgCoords>::new();
+    let mut features_nonmax = Vec::<Corner>::new();
     timeit!({detector_nonmax.detect(&image_grey, &mut features_nonmax)});
 
     // detect faetures
-    let mut features = Vec::<ImgCoords>::new();
+    let mut features = Vec::<Corner>::new();
     timeit!({detector.detect(&image_grey, &mut features)});
 
     // draw features over image - nonmax
@@ -6,10 +6,11 @@
 
 use image::GrayImage;
 use std::cmp;
+use std::collections::HashMap;
 use std::vec::Vec;
 
 use crate::traits::KeypointDetector;
-use crate::utils::ImgCoords;
+use crate::utils::{self, Corner, ImgCoords};
 
 #[derive(Default, Debug)]
 pub struct FASTDetector {
@@ -27,80 +28,84 @@ enum ComparedToCentre {
 }
 
 impl FASTDetector {
-    const NEIGHBOR_RELATIVE_COORDS: [(i8, i8); 16] = [
-        (0, -3),  // 1
-        (1, -3),  // 2
-        (2, -2),  // 3
-        (3, -1),  // 4
-        (3, 0),   // 5
-        (3, 1),   // 6
-        (2, 2),   // 7
-        (1, 3),   // 8
-        (0, 3),   // 9
-        (-1, 3),  // 10
-        (-2, 2),  // 11
-        (-3, 1),  // 12
-        (-3, 0),  // 13
-        (-3, -1), // 14
-        (-2, -2), // 15
-        (-1, -3), // 16
-    ];
+    /// [`Self::threshold_tab`] code for a neighbor significantly darker than the centre pixel
+    const TAB_DARKER: u8 = 1;
+    /// [`Self::threshold_tab`] code for a neighbor significantly brighter than the centre pixel
+    const TAB_BRIGHTER: u8 = 2;
 
-    /// decide whether `pix_x` is within the pixel intensity bounds of `pix_p`:
-    /// - `pix_p` is the central pixel
-    /// - `pix_x` is one of the neighboring pixels
-    fn within_bounds(&self, pix_p: u8, pix_x: u8) -> bool {
-        !self.is_black(pix_p, pix_x) && !self.is_white(pix_p, pix_x)
+    fn is_black(pix_p: u8, pix_x: u8, threshold: u8) -> bool {
+        (pix_x as u16) + (threshold as u16) < pix_p as u16
     }
-
-    fn is_black(&self, pix_p: u8, pix_x: u8) -> bool {
-        (pix_x as u16) + (self.params.threshold as u16) < pix_p as u16
+    fn is_white(pix_p: u8, pix_x: u8, threshold: u8) -> bool {
+        (pix_x as i16) - (threshold as i16) > pix_p as i16
     }
-    fn is_white(&self, pix_p: u8, pix_x: u8) -> bool {
-        (pix_x as i16) - (self.params.threshold as i16) > pix_p as i16
+
+    /// Number of equally-spaced probe pixels to sample around the circle before running the
+    /// full contiguous-arc test, and how many of them must agree (be all-brighter or
+    /// all-darker than the centre) for the pixel to be worth testing further.
+    ///
+    /// For an arc of length `N` on a circle of `P` points, `ceil(P / (P - N + 1))` probes are
+    /// sampled `P / num_probes` apart, and any contiguous arc of pixels that are all brighter (or
+    /// all darker) than the centre must cover at least `num_probes - 1` of them (and at least 1) -
+    /// this is the generalized "cross" test from the original paper (there it specializes to the 4 points at 1, 5, 9, 13
+    /// for FAST-12/16, of which at least 3 must agree). That derivation is only a correct
+    /// necessary condition for the four documented pairings below - for an arbitrary `N` in range
+    /// the probes may not land evenly enough apart to guarantee a real arc can't hide between
+    /// them, which would silently reject pixels the full contiguous-arc test would still accept.
+    /// So validate the pairing itself rather than just that `N` is in range.
+    fn probe_plan(&self) -> (usize, usize) {
+        let p = self.params.pattern.num_points() as usize;
+        let n = self.params.min_contig_neighbors as usize;
+        assert!(
+            matches!(
+                (self.params.pattern, n),
+                (FASTPattern::Circle8, 5)
+                    | (FASTPattern::Circle12, 7)
+                    | (FASTPattern::Circle16, 9)
+                    | (FASTPattern::Circle16, 12)
+            ),
+            "min_contig_neighbors ({n}) is not a documented arc length for pattern {:?}; valid \
+             pairings are Circle8+5, Circle12+7, Circle16+9, Circle16+12",
+            self.params.pattern
+        );
+        let num_probes = (p + (p - n + 1) - 1) / (p - n + 1);
+        let required = cmp::max(1, num_probes - 1);
+        (num_probes, required)
     }
 
-    /// Do a first pass via some of the neighbors to decide whether the pixel may is a valid corner
-    /// candidate
+    /// Do a first pass via a handful of the neighbors to decide whether the pixel may be a valid
+    /// corner candidate, without running the full contiguous-arc test.
     /// TODO Return a hint for the bounds of the potential corner
     fn high_speed_test(&self, img: &GrayImage, coords: ImgCoords) -> bool {
         let p: u8 = img.get_pixel(coords.x, coords.y)[0];
-
-        // neighbor coordinates relative to the central pixel
-        let up: u8 = img.get_pixel(coords.x, coords.y - 3)[0];
-        let right: u8 = img.get_pixel(coords.x + 3, coords.y)[0];
-        let down: u8 = img.get_pixel(coords.x, coords.y + 3)[0];
-        let left: u8 = img.get_pixel(coords.x - 3, coords.y)[0];
-
-        if self.within_bounds(p, up) && self.within_bounds(p, down) {
-            return false;
-        }
-        // if both black or both white
-        else if self.is_black(p, up) && self.is_black(p, down) {
-            if self.is_black(p, right) || self.is_black(p, left) {
-                return true;
-            }
-        } else if self.is_white(p, up) && self.is_white(p, down) {
-            if self.is_white(p, right) || self.is_white(p, left) {
-                return true;
-            }
-        }
-        // if one is black or one is white
-        else if self.is_black(p, up) || self.is_black(p, down) {
-            if self.is_black(p, right) && self.is_black(p, left) {
-                return true;
-            }
-        } else if self.is_white(p, up) || self.is_white(p, down) {
-            if self.is_white(p, right) && self.is_white(p, left) {
-                return true;
+        let circle = self.params.pattern.circle_coords();
+
+        let (num_probes, required) = self.probe_plan();
+        let step = circle.len() / num_probes;
+
+        let mut black_count = 0;
+        let mut white_count = 0;
+        for i in 0..num_probes {
+            let (dx, dy) = circle[i * step];
+            let probe = img.get_pixel(
+                (coords.x as i64 + dx as i64) as u32,
+                (coords.y as i64 + dy as i64) as u32,
+            )[0];
+
+            if Self::is_black(p, probe, self.params.threshold) {
+                black_count += 1;
+            } else if Self::is_white(p, probe, self.params.threshold) {
+                white_count += 1;
             }
         }
 
-        false
+        black_count >= required || white_count >= required
     }
 
-    fn neighbor_vals(img: &GrayImage, coords: ImgCoords) -> Vec<u8> {
-        Self::NEIGHBOR_RELATIVE_COORDS
+    fn neighbor_vals(&self, img: &GrayImage, coords: ImgCoords) -> Vec<u8> {
+        self.params
+            .pattern
+            .circle_coords()
             .iter()
             .map(|n_coords| -> u8 {
                 img.get_pixel(
@@ -111,30 +116,56 @@ impl FASTDetector {
             .collect()
     }
 
-    fn neighbor_tags(
+    /// Decide whether `coords` is a corner under the contiguous-arc test, using `threshold`
+    /// rather than `self.params.threshold` so that [`Self::get_score`] can binary-search it.
+    ///
+    /// Dispatches to the table-driven classifier ([`Self::is_corner_at_threshold_lut`]) unless
+    /// [`FASTDetectorParams::use_lut_classifier`] opts back into the original allocation-heavy
+    /// implementation, kept around to validate the two against each other. `tab_cache` memoizes
+    /// [`Self::threshold_tab`] per distinct `threshold` tried across a whole `detect()` call,
+    /// since the same threshold is reused for every candidate pixel and often recurs across
+    /// different pixels' [`Self::get_score`] binary searches.
+    fn is_corner_at_threshold(
         &self,
-        neighbor_vals: &Vec<u8>,
-        central_pixel_val: u8,
-    ) -> Vec<ComparedToCentre> {
-        neighbor_vals
+        img: &GrayImage,
+        coords: ImgCoords,
+        threshold: u8,
+        tab_cache: &mut HashMap<u8, [u8; 512]>,
+    ) -> bool {
+        if self.params.use_lut_classifier {
+            let tab = tab_cache
+                .entry(threshold)
+                .or_insert_with(|| Self::threshold_tab(threshold));
+            self.is_corner_at_threshold_lut(img, coords, tab)
+        } else {
+            self.is_corner_at_threshold_legacy(img, coords, threshold)
+        }
+    }
+
+    /// Original contiguous-arc test: tags every neighbor relative to the centre pixel into a
+    /// `Vec`, then scans it for a run of `min_contig_neighbors` `Black` or `White` tags. Allocates on
+    /// every call; kept only so [`Self::is_corner_at_threshold`] can fall back to it.
+    fn is_corner_at_threshold_legacy(
+        &self,
+        img: &GrayImage,
+        coords: ImgCoords,
+        threshold: u8,
+    ) -> bool {
+        let p = img.get_pixel(coords.x, coords.y)[0];
+        let neighbor_vals = self.neighbor_vals(img, coords);
+
+        let mut neighbor_tags: Vec<ComparedToCentre> = neighbor_vals
             .iter()
             .map(|n_val| -> ComparedToCentre {
-                if self.is_black(central_pixel_val, *n_val) {
+                if Self::is_black(p, *n_val, threshold) {
                     ComparedToCentre::Black
-                } else if self.is_white(central_pixel_val, *n_val) {
+                } else if Self::is_white(p, *n_val, threshold) {
                     ComparedToCentre::White
                 } else {
                     ComparedToCentre::InBounds
                 }
             })
-            .collect()
-    }
-
-    fn check_pixel(&self, img: &GrayImage, coords: ImgCoords) -> bool {
-        let p = img.get_pixel(coords.x, coords.y)[0];
-        let neighbor_vals = Self::neighbor_vals(img, coords);
-
-        let mut neighbor_tags: Vec<ComparedToCentre> = self.neighbor_tags(&neighbor_vals, p);
+            .collect();
         // repeat the first min_contig_neighbors elements so that you can loop until the very last
         // element of the neighbor_tags
         neighbor_tags.extend(neighbor_tags[..self.params.min_contig_neighbors as usize].to_vec());
@@ -162,67 +193,122 @@ impl FASTDetector {
         false
     }
 
-    /// Compute a score for a pixel already identified as a corner
-    /// See Eq. 8 of "Machine learning for high-speed corner detection" paper.
-    fn get_score(&self, img: &GrayImage, coords: ImgCoords) -> u32 {
+    /// Table-driven contiguous-arc test: classify each neighbor via the precomputed `tab` (see
+    /// [`Self::threshold_tab`]) into a `darker`/`brighter` bitmask (one bit per circle point, no
+    /// heap allocation), bail out early via the same cross-probe reject as
+    /// [`Self::high_speed_test`] read off those masks, then look for a run of
+    /// `min_contig_neighbors` set bits in either mask.
+    fn is_corner_at_threshold_lut(
+        &self,
+        img: &GrayImage,
+        coords: ImgCoords,
+        tab: &[u8; 512],
+    ) -> bool {
         let p = img.get_pixel(coords.x, coords.y)[0];
-        let neighbor_vals = Self::neighbor_vals(img, coords);
-        let neighbor_tags: Vec<ComparedToCentre> = self.neighbor_tags(&neighbor_vals, p);
-
-        let mut sum_black: u32 = 0;
-        let mut sum_white: u32 = 0;
-        for i in 0..neighbor_vals.len() {
-            if neighbor_tags[i] == ComparedToCentre::Black {
-                sum_black += ((neighbor_vals[i] as i16 - p as i16).abs()
-                    - (self.params.threshold as i16)) as u32;
-            } else if neighbor_tags[i] == ComparedToCentre::White {
-                sum_white += ((neighbor_vals[i] as i16 - p as i16).abs()
-                    - (self.params.threshold as i16)) as u32;
+        let circle = self.params.pattern.circle_coords();
+        let num_points = circle.len() as u32;
+
+        let mut darker_mask: u32 = 0;
+        let mut brighter_mask: u32 = 0;
+        for (i, (dx, dy)) in circle.iter().enumerate() {
+            let val = img.get_pixel(
+                (coords.x as i64 + *dx as i64) as u32,
+                (coords.y as i64 + *dy as i64) as u32,
+            )[0];
+            let d = (i16::from(val) - i16::from(p) + 255) as usize;
+            match tab[d] {
+                Self::TAB_DARKER => darker_mask |= 1 << i,
+                Self::TAB_BRIGHTER => brighter_mask |= 1 << i,
+                _ => {}
             }
         }
 
-        cmp::max(sum_black, sum_white)
-    }
+        // cross-probe reject, read off the masks we already built instead of resampling pixels
+        let (num_probes, required) = self.probe_plan();
+        let step = num_points as usize / num_probes;
+        let probe_bits: u32 = (0..num_probes).map(|i| 1 << (i * step)).sum();
+        if (darker_mask & probe_bits).count_ones() < required as u32
+            && (brighter_mask & probe_bits).count_ones() < required as u32
+        {
+            return false;
+        }
 
-    fn nonmax_suppression(&self, img: &GrayImage, features: &mut Vec<ImgCoords>) {
-        let mut indices_to_remove: Vec<usize> = vec![];
-        // TODO probaly not optimal - Rewrite
-        for (idx, feat) in features.iter().enumerate() {
-            // check for neighbors - keep the pixel with the biggest sum of absolute diffs
-            for idx2 in idx + 1..features.len() {
-                // skip if already marked as remove
-                if indices_to_remove.contains(&idx2) {
-                    continue;
-                }
+        let run_len = self.params.min_contig_neighbors;
+        Self::has_contiguous_run(darker_mask, num_points, run_len)
+            || Self::has_contiguous_run(brighter_mask, num_points, run_len)
+    }
 
-                let feat2 = &features[idx2];
+    /// Whether `mask` (one bit per circle point) contains a run of `run_len` consecutive set
+    /// bits, wrapping around the `num_points`-point ring.
+    fn has_contiguous_run(mask: u32, num_points: u32, run_len: u8) -> bool {
+        let doubled = mask | (mask << num_points);
+        let run_mask: u32 = (1u32 << run_len) - 1;
+        (0..num_points).any(|shift| (doubled >> shift) & run_mask == run_mask)
+    }
 
-                // neighbors?
-                if (feat2.x as i64 - feat.x as i64).abs() > 1
-                    || (feat2.y as i64 - feat.y as i64).abs() > 1
-                {
-                    continue;
-                }
+    /// Build, for the given `threshold`, how a neighbor pixel compares to the centre pixel from
+    /// the difference `d = (neighbor as i16 - centre as i16) + 255` alone: [`Self::TAB_DARKER`]
+    /// if `d < 255 - threshold`, [`Self::TAB_BRIGHTER`] if `d > 255 + threshold`, `0` otherwise.
+    /// Matches [`Self::is_black`]/[`Self::is_white`]'s strict comparisons exactly. Callers should
+    /// go through the `tab_cache` in [`Self::is_corner_at_threshold`] rather than calling this
+    /// directly, since it's rebuilt for the same `threshold` far more often than it changes.
+    fn threshold_tab(threshold: u8) -> [u8; 512] {
+        let lo = 255 - usize::from(threshold);
+        let hi = 255 + usize::from(threshold);
+        let mut tab = [0u8; 512];
+        for (d, slot) in tab.iter_mut().enumerate() {
+            *slot = if d < lo {
+                Self::TAB_DARKER
+            } else if d > hi {
+                Self::TAB_BRIGHTER
+            } else {
+                0
+            };
+        }
+        tab
+    }
 
-                // keep pixel with highest score - mark other as removed
-                let score1 = self.get_score(img, *feat);
-                let score2 = self.get_score(img, *feat2);
+    fn check_pixel(
+        &self,
+        img: &GrayImage,
+        coords: ImgCoords,
+        tab_cache: &mut HashMap<u8, [u8; 512]>,
+    ) -> bool {
+        self.is_corner_at_threshold(img, coords, self.params.threshold, tab_cache)
+    }
 
-                // if remove idx1
-                if score1 <= score2 {
-                    indices_to_remove.push(idx);
-                    break;
-                } else {
-                    indices_to_remove.push(idx2);
-                    continue;
-                }
+    /// Compute a score for a pixel already identified as a corner: the maximal threshold `t`
+    /// for which the pixel still qualifies as a corner under the contiguous-arc test, found by
+    /// binary-searching `t` over [`Self::is_corner_at_threshold`]. Unlike a sum of absolute
+    /// differences, this makes scores directly comparable and stable across images.
+    fn get_score(
+        &self,
+        img: &GrayImage,
+        coords: ImgCoords,
+        tab_cache: &mut HashMap<u8, [u8; 512]>,
+    ) -> f32 {
+        let mut low = self.params.threshold; // known to still be a corner
+        let mut high = u8::MAX;
+        while low < high {
+            // bias the midpoint up so that `low` converges without looping forever
+            let mid = low + (high - low + 1) / 2;
+            if self.is_corner_at_threshold(img, coords, mid, tab_cache) {
+                low = mid;
+            } else {
+                high = mid - 1;
             }
         }
 
-        // remove
-        let mut i: usize = 0;
-        features.retain(|_| (!indices_to_remove.contains(&i), i += 1).0);
-    } // nonmax_suppression
+        low as f32
+    }
+
+    fn nonmax_suppression(&self, features: &mut Vec<Corner>) {
+        utils::suppress_non_max(features, self.params.nms_radius);
+
+        if let Some(target) = self.params.max_corners {
+            utils::suppress_to_target(features, self.params.nms_radius, target);
+        }
+    }
 } // impl FASTDetector
 
 impl KeypointDetector for FASTDetector {
@@ -238,30 +324,156 @@ impl KeypointDetector for FASTDetector {
         &self.params
     }
 
-    // TODO - add a mask argument for applying the detector only at a part of the image
-    fn detect(&self, img: &Self::ImageView, features: &mut Vec<ImgCoords>) {
-        // iterate over all pixels - ignore first and last 3 rows and columns
-        for row in 3..img.height() - 3 {
-            for col in 3..img.width() - 3 {
-                let coords = ImgCoords::new(col, row);
-                // high-speed test
-                // TODO For now run it only when N == 12
-                if self.params.do_high_speed_test && self.params.min_contig_neighbors == 12 {
-                    if !self.high_speed_test(img, coords) {
+    /// `detect_in_mask` skips a `pattern.radius()`-wide border margin on every side, so the image
+    /// must be at least `2 * margin + 1` pixels wide/tall for that margin to leave anything in
+    /// the middle.
+    fn min_image_size(&self) -> u32 {
+        2 * self.params.pattern.radius() + 1
+    }
+
+    fn detect(&self, img: &Self::ImageView, features: &mut Vec<Corner>) {
+        self.detect_in_mask(img, None, features);
+    }
+
+    fn detect_in_mask(
+        &self,
+        img: &Self::ImageView,
+        mask: Option<&Self::ImageView>,
+        features: &mut Vec<Corner>,
+    ) {
+        if let Some(mask) = mask {
+            assert_eq!(
+                (mask.width(), mask.height()),
+                (img.width(), img.height()),
+                "mask dimensions must match the image being detected in"
+            );
+        }
+
+        // validate pattern/min_contig_neighbors up front, regardless of `do_high_speed_test`: the
+        // legacy classifier would otherwise only discover a bad pairing via a confusing
+        // out-of-bounds slice panic once it actually reaches a candidate pixel
+        self.probe_plan();
+
+        // memoizes threshold_tab per distinct threshold across the whole call, instead of
+        // rebuilding it for every candidate pixel
+        let mut tab_cache: HashMap<u8, [u8; 512]> = HashMap::new();
+
+        // iterate over all pixels - ignore the border rows/columns that the chosen pattern's
+        // circle would read outside of the image
+        let margin = self.params.pattern.radius();
+        for row in margin..img.height() - margin {
+            for col in margin..img.width() - margin {
+                if let Some(mask) = mask {
+                    if mask.get_pixel(col, row)[0] == 0 {
                         continue;
                     }
                 }
 
+                let coords = ImgCoords::new(col, row);
+                // high-speed test
+                if self.params.do_high_speed_test && !self.high_speed_test(img, coords) {
+                    continue;
+                }
+
                 // detect
-                if self.check_pixel(img, coords) {
-                    features.push(coords);
+                if self.check_pixel(img, coords, &mut tab_cache) {
+                    let score = self.get_score(img, coords, &mut tab_cache);
+                    features.push(Corner::new(coords.x, coords.y, score));
                 }
             }
         }
 
         // non-maximal suppression
         if self.params.do_nonmax_suppression {
-            self.nonmax_suppression(img, features);
+            self.nonmax_suppression(features);
+        }
+    }
+}
+
+// FASTPattern -------------------------------------------------------------------------------------
+
+/// The Bresenham circle used to probe around the candidate pixel, paired with the arc length
+/// that is commonly tested on it. These correspond to the variants described in the original
+/// paper and its follow-ups: FAST-5-8 and FAST-7-12 for the smaller, cheaper circles and
+/// FAST-9-16 / FAST-12-16 for the standard 16-pixel circle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FASTPattern {
+    /// 8 pixels on a radius-1 circle - pairs with an arc length of 5 (FAST-5-8)
+    Circle8,
+    /// 12 pixels on a radius-2 Bresenham circle - pairs with an arc length of 7 (FAST-7-12)
+    Circle12,
+    /// 16 pixels on a radius-3 Bresenham circle - pairs with an arc length of 9 or 12
+    /// (FAST-9-16 / FAST-12-16)
+    Circle16,
+}
+
+impl FASTPattern {
+    const CIRCLE_8: [(i8, i8); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+
+    const CIRCLE_12: [(i8, i8); 12] = [
+        (0, -2),
+        (1, -2),
+        (2, -1),
+        (2, 0),
+        (2, 1),
+        (1, 2),
+        (0, 2),
+        (-1, 2),
+        (-2, 1),
+        (-2, 0),
+        (-2, -1),
+        (-1, -2),
+    ];
+
+    const CIRCLE_16: [(i8, i8); 16] = [
+        (0, -3),
+        (1, -3),
+        (2, -2),
+        (3, -1),
+        (3, 0),
+        (3, 1),
+        (2, 2),
+        (1, 3),
+        (0, 3),
+        (-1, 3),
+        (-2, 2),
+        (-3, 1),
+        (-3, 0),
+        (-3, -1),
+        (-2, -2),
+        (-1, -3),
+    ];
+
+    /// Coordinates (relative to the central pixel) of the circle's points, in ring order
+    fn circle_coords(self) -> &'static [(i8, i8)] {
+        match self {
+            Self::Circle8 => &Self::CIRCLE_8,
+            Self::Circle12 => &Self::CIRCLE_12,
+            Self::Circle16 => &Self::CIRCLE_16,
+        }
+    }
+
+    /// Number of points `P` on the circle
+    fn num_points(self) -> u8 {
+        self.circle_coords().len() as u8
+    }
+
+    /// Number of border rows/columns that must be skipped so the circle never reads outside the
+    /// image
+    fn radius(self) -> u32 {
+        match self {
+            Self::Circle8 => 1,
+            Self::Circle12 => 2,
+            Self::Circle16 => 3,
         }
     }
 }
@@ -274,19 +486,181 @@ pub struct FASTDetectorParams {
     /// Intensity threshold (0, 255) for determining whether the intensity of a neighboring pixel
     /// is significantly higher or lower than the central pixel
     pub threshold: u8,
-    /// Number of neighbors to consider when determining whether a pixel is a corner
+    /// Circle of points probed around each candidate pixel
+    pub pattern: FASTPattern,
+    /// Number of contiguous neighbors (out of `pattern`'s circle) required for a pixel to be
+    /// considered a corner, e.g. 9 or 12 for [`FASTPattern::Circle16`]
     pub min_contig_neighbors: u8,
     pub do_high_speed_test: bool,
     pub do_nonmax_suppression: bool,
+    /// Use the allocation-free, table-driven contiguous-arc classifier. Disable to fall back to
+    /// the original `Vec`-based implementation, kept around to validate the two against each
+    /// other.
+    pub use_lut_classifier: bool,
+    /// Radius (in pixels) within which non-maximum suppression keeps only the strongest corner
+    pub nms_radius: u32,
+    /// If set, after non-maximum suppression keep at most `max_corners` corners, growing the
+    /// suppression radius as needed to thin out the rest (adaptive non-maximal suppression), and
+    /// only keeping the `max_corners` strongest if growing the radius stops helping. The
+    /// survivors are local maxima, not necessarily the globally strongest corners, and can be
+    /// fewer than `max_corners`
+    pub max_corners: Option<usize>,
 }
 
 impl Default for FASTDetectorParams {
     fn default() -> Self {
         Self {
             threshold: 10,
+            pattern: FASTPattern::Circle16,
             min_contig_neighbors: 12,
             do_high_speed_test: true,
             do_nonmax_suppression: true,
+            use_lut_classifier: true,
+            nms_radius: 1,
+            max_corners: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    /// Build a square image filled with `base`, with the first `dark_run` points of `pattern`'s
+    /// circle (in ring order, starting from index 0) set to `dark` - a minimal, unambiguous
+    /// contiguous arc around the centre pixel.
+    fn ring_image(pattern: FASTPattern, dark_run: usize, base: u8, dark: u8) -> (GrayImage, ImgCoords) {
+        let margin = pattern.radius();
+        let centre = margin + 2;
+        let size = centre * 2 + 1;
+
+        let mut img = GrayImage::from_pixel(size, size, Luma([base]));
+        for (i, (dx, dy)) in pattern.circle_coords().iter().enumerate() {
+            if i < dark_run {
+                let x = (centre as i64 + *dx as i64) as u32;
+                let y = (centre as i64 + *dy as i64) as u32;
+                img.put_pixel(x, y, Luma([dark]));
+            }
+        }
+        (img, ImgCoords::new(centre, centre))
+    }
+
+    fn detector_for(pattern: FASTPattern, min_contig_neighbors: u8, use_lut_classifier: bool) -> FASTDetector {
+        FASTDetector {
+            params: FASTDetectorParams {
+                threshold: 20,
+                pattern,
+                min_contig_neighbors,
+                do_high_speed_test: false,
+                do_nonmax_suppression: false,
+                use_lut_classifier,
+                ..FASTDetectorParams::default()
+            },
+        }
+    }
+
+    /// For every pattern/arc-length combo the doc comments advertise as valid, a contiguous run
+    /// of exactly `min_contig_neighbors` much-darker neighbors is a corner, but one pixel short of
+    /// that run is not - under both the legacy and the table-driven classifier.
+    #[test]
+    fn known_corner_pattern_per_combo() {
+        for &(pattern, run_len) in &[
+            (FASTPattern::Circle8, 5u8),
+            (FASTPattern::Circle12, 7u8),
+            (FASTPattern::Circle16, 9u8),
+            (FASTPattern::Circle16, 12u8),
+        ] {
+            for use_lut_classifier in [false, true] {
+                let detector = detector_for(pattern, run_len, use_lut_classifier);
+                let mut tab_cache = HashMap::new();
+
+                let (corner_img, coords) = ring_image(pattern, run_len as usize, 128, 0);
+                assert!(
+                    detector.is_corner_at_threshold(&corner_img, coords, 20, &mut tab_cache),
+                    "{:?}+{} with a full contiguous run should be a corner (lut={})",
+                    pattern,
+                    run_len,
+                    use_lut_classifier
+                );
+
+                let (short_img, coords) = ring_image(pattern, run_len as usize - 1, 128, 0);
+                assert!(
+                    !detector.is_corner_at_threshold(&short_img, coords, 20, &mut tab_cache),
+                    "{:?}+{} one pixel short of a contiguous run should not be a corner (lut={})",
+                    pattern,
+                    run_len,
+                    use_lut_classifier
+                );
+            }
+        }
+    }
+
+    /// `get_score` binary-searches for the maximal threshold the pixel still qualifies as a
+    /// corner under - build a centre/neighbor pair whose exact breakeven point is known
+    /// (`is_black` stops holding once `threshold >= centre - dark`) and assert it finds exactly
+    /// that value, under both classifiers.
+    #[test]
+    fn get_score_finds_exact_max_tolerable_threshold() {
+        let pattern = FASTPattern::Circle16;
+        let run_len = 9u8;
+        let (base, dark) = (128u8, 0u8);
+        let (img, coords) = ring_image(pattern, run_len as usize, base, dark);
+        let expected_score = (base - dark - 1) as f32;
+
+        for use_lut_classifier in [false, true] {
+            let detector = detector_for(pattern, run_len, use_lut_classifier);
+            let mut tab_cache = HashMap::new();
+            assert_eq!(
+                detector.get_score(&img, coords, &mut tab_cache),
+                expected_score,
+                "lut={use_lut_classifier}"
+            );
+        }
+    }
+
+    /// The table-driven classifier was introduced to replace the allocation-heavy legacy one
+    /// without changing behavior - running the same image through both should agree pixel for
+    /// pixel, corner for corner.
+    #[test]
+    fn legacy_and_lut_classifiers_agree() {
+        let (w, h) = (40, 40);
+        let img = GrayImage::from_fn(w, h, |x, y| Luma([((x * 31 + y * 17) % 256) as u8]));
+
+        let mut legacy_features = Vec::new();
+        FASTDetector {
+            params: FASTDetectorParams {
+                use_lut_classifier: false,
+                ..FASTDetectorParams::default()
+            },
+        }
+        .detect(&img, &mut legacy_features);
+
+        let mut lut_features = Vec::new();
+        FASTDetector {
+            params: FASTDetectorParams {
+                use_lut_classifier: true,
+                ..FASTDetectorParams::default()
+            },
+        }
+        .detect(&img, &mut lut_features);
+
+        assert!(!legacy_features.is_empty(), "test image should contain some corners");
+        assert_eq!(legacy_features, lut_features);
+    }
+
+    #[test]
+    #[should_panic(expected = "min_contig_neighbors")]
+    fn mismatched_pattern_and_min_contig_neighbors_panics() {
+        let detector = FASTDetector {
+            params: FASTDetectorParams {
+                pattern: FASTPattern::Circle8,
+                min_contig_neighbors: 12,
+                ..FASTDetectorParams::default()
+            },
+        };
+        let img = GrayImage::from_pixel(20, 20, Luma([128]));
+        let mut features = Vec::new();
+        detector.detect(&img, &mut features);
+    }
+}
@@ -0,0 +1,243 @@
+//! This module provides an implementation of the Harris corner detector
+//! See <https://en.wikipedia.org/wiki/Harris_corner_detector> or [the original
+//! paper](http://www.bmva.org/bmvc/1988/avc-88-023.pdf) for more information
+
+// HarrisDetector -----------------------------------------------------------------------------------
+
+use image::GrayImage;
+use std::vec::Vec;
+
+use crate::traits::KeypointDetector;
+use crate::utils::{self, Corner};
+
+#[derive(Default, Debug)]
+pub struct HarrisDetector {
+    pub params: HarrisDetectorParams,
+}
+
+impl HarrisDetector {
+    const SOBEL_X: [[i32; 3]; 3] = [[-1, 0, 1], [-2, 0, 2], [-1, 0, 1]];
+    const SOBEL_Y: [[i32; 3]; 3] = [[-1, -2, -1], [0, 0, 0], [1, 2, 1]];
+
+    /// Horizontal/vertical image gradients `(Ix, Iy)` via a 3x3 Sobel filter, as `width * height`
+    /// row-major fields
+    fn gradients(img: &GrayImage) -> (Vec<f32>, Vec<f32>) {
+        let (w, h) = (img.width(), img.height());
+        let mut ix = vec![0.0f32; (w * h) as usize];
+        let mut iy = vec![0.0f32; (w * h) as usize];
+
+        for row in 1..h - 1 {
+            for col in 1..w - 1 {
+                let mut gx = 0i32;
+                let mut gy = 0i32;
+                for (ky, krow_x) in Self::SOBEL_X.iter().enumerate() {
+                    for (kx, &wx) in krow_x.iter().enumerate() {
+                        let pixel =
+                            img.get_pixel(col + kx as u32 - 1, row + ky as u32 - 1)[0] as i32;
+                        gx += wx * pixel;
+                        gy += Self::SOBEL_Y[ky][kx] * pixel;
+                    }
+                }
+                let idx = (row * w + col) as usize;
+                ix[idx] = gx as f32;
+                iy[idx] = gy as f32;
+            }
+        }
+
+        (ix, iy)
+    }
+
+    /// 1-D Gaussian kernel of the given standard deviation, covering `2 * radius + 1` taps
+    fn gaussian_kernel(sigma: f32, radius: u32) -> Vec<f32> {
+        let radius = radius as i32;
+        let mut kernel: Vec<f32> = (-radius..=radius)
+            .map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let sum: f32 = kernel.iter().sum();
+        for weight in &mut kernel {
+            *weight /= sum;
+        }
+        kernel
+    }
+
+    /// Separable convolution of a `w x h` field with a 1-D kernel, applied horizontally then
+    /// vertically, clamping at the image border
+    fn smooth(field: &[f32], w: u32, h: u32, kernel: &[f32]) -> Vec<f32> {
+        let radius = (kernel.len() / 2) as i64;
+        let (w, h) = (i64::from(w), i64::from(h));
+
+        let mut horizontal = vec![0.0f32; (w * h) as usize];
+        for row in 0..h {
+            for col in 0..w {
+                let mut acc = 0.0f32;
+                for (k, &weight) in kernel.iter().enumerate() {
+                    let x = (col + k as i64 - radius).clamp(0, w - 1);
+                    acc += weight * field[(row * w + x) as usize];
+                }
+                horizontal[(row * w + col) as usize] = acc;
+            }
+        }
+
+        let mut out = vec![0.0f32; (w * h) as usize];
+        for row in 0..h {
+            for col in 0..w {
+                let mut acc = 0.0f32;
+                for (k, &weight) in kernel.iter().enumerate() {
+                    let y = (row + k as i64 - radius).clamp(0, h - 1);
+                    acc += weight * horizontal[(y * w + col) as usize];
+                }
+                out[(row * w + col) as usize] = acc;
+            }
+        }
+
+        out
+    }
+
+    fn nonmax_suppression(&self, features: &mut Vec<Corner>) {
+        utils::suppress_non_max(features, self.params.nms_radius);
+
+        if let Some(target) = self.params.max_corners {
+            utils::suppress_to_target(features, self.params.nms_radius, target);
+        }
+    }
+} // impl HarrisDetector
+
+impl KeypointDetector for HarrisDetector {
+    type Params = HarrisDetectorParams;
+    type ImageView = GrayImage;
+
+    fn new() -> Self {
+        Self {
+            params: HarrisDetectorParams::default(),
+        }
+    }
+    fn get_params(&self) -> &Self::Params {
+        &self.params
+    }
+
+    /// `detect` skips a `1 + window_radius`-wide border margin on every side (the Sobel pass
+    /// leaves the outermost ring unfilled, and the Gaussian window reaches `window_radius` pixels
+    /// further still), so the image must be at least `2 * margin + 1` pixels wide/tall.
+    fn min_image_size(&self) -> u32 {
+        2 * (1 + self.params.window_radius) + 1
+    }
+
+    fn detect(&self, img: &Self::ImageView, features: &mut Vec<Corner>) {
+        let (w, h) = (img.width(), img.height());
+        let (ix, iy) = Self::gradients(img);
+
+        let ixx: Vec<f32> = ix.iter().map(|v| v * v).collect();
+        let iyy: Vec<f32> = iy.iter().map(|v| v * v).collect();
+        let ixy: Vec<f32> = ix.iter().zip(iy.iter()).map(|(a, b)| a * b).collect();
+
+        let kernel = Self::gaussian_kernel(self.params.sigma, self.params.window_radius);
+        let sxx = Self::smooth(&ixx, w, h, &kernel);
+        let syy = Self::smooth(&iyy, w, h, &kernel);
+        let sxy = Self::smooth(&ixy, w, h, &kernel);
+
+        // ignore the border the Sobel filter couldn't fill in, plus the window the Gaussian
+        // smoothing pass pulls in from beyond that border
+        let margin = 1 + self.params.window_radius;
+        for row in margin..h - margin {
+            for col in margin..w - margin {
+                let idx = (row * w + col) as usize;
+                let (sxx, syy, sxy) = (sxx[idx], syy[idx], sxy[idx]);
+
+                // R = det(M) - k * trace(M)^2, M = [[Sxx, Sxy], [Sxy, Syy]]
+                let det = sxx * syy - sxy * sxy;
+                let trace = sxx + syy;
+                let response = det - self.params.k * trace * trace;
+
+                if response > self.params.response_threshold {
+                    features.push(Corner::new(col, row, response));
+                }
+            }
+        }
+
+        if self.params.do_nonmax_suppression {
+            self.nonmax_suppression(features);
+        }
+    }
+}
+
+// HarrisDetectorParams -------------------------------------------------------------------------
+
+/// Parameters of the [`HarrisDetector`]
+#[derive(Debug)]
+pub struct HarrisDetectorParams {
+    /// Sensitivity constant in the Harris response `R = det(M) - k * trace(M)^2`, typically
+    /// between 0.04 and 0.06
+    pub k: f32,
+    /// Standard deviation of the Gaussian window used to smooth the structure tensor
+    pub sigma: f32,
+    /// Radius (in pixels) of the Gaussian window, i.e. the window is `2 * window_radius + 1`
+    /// pixels wide
+    pub window_radius: u32,
+    /// Minimum Harris response for a pixel to be considered a corner. Depends on image contrast
+    /// and `window_radius`/`sigma`, so this typically needs tuning per use case
+    pub response_threshold: f32,
+    pub do_nonmax_suppression: bool,
+    /// Radius (in pixels) within which non-maximum suppression keeps only the strongest corner
+    pub nms_radius: u32,
+    /// If set, after non-maximum suppression keep at most `max_corners` corners, growing the
+    /// suppression radius as needed to thin out the rest (adaptive non-maximal suppression), and
+    /// only keeping the `max_corners` strongest if growing the radius stops helping. The
+    /// survivors are local maxima, not necessarily the globally strongest corners, and can be
+    /// fewer than `max_corners`
+    pub max_corners: Option<usize>,
+}
+
+impl Default for HarrisDetectorParams {
+    fn default() -> Self {
+        Self {
+            k: 0.04,
+            sigma: 1.0,
+            window_radius: 2,
+            response_threshold: 1e5,
+            do_nonmax_suppression: true,
+            nms_radius: 1,
+            max_corners: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    /// A white square on a black background, large enough to clear the Sobel/Gaussian margins,
+    /// with unambiguous corners at its four vertices.
+    fn square_image() -> GrayImage {
+        GrayImage::from_fn(40, 40, |x, y| {
+            if (10..30).contains(&x) && (10..30).contains(&y) {
+                Luma([255])
+            } else {
+                Luma([0])
+            }
+        })
+    }
+
+    #[test]
+    fn detects_square_corners() {
+        let img = square_image();
+        let detector = HarrisDetector::new();
+
+        let mut features = Vec::new();
+        detector.detect(&img, &mut features);
+
+        assert!(!features.is_empty(), "should find at least one corner on a square");
+
+        for &(cx, cy) in &[(10u32, 10u32), (10, 29), (29, 10), (29, 29)] {
+            assert!(
+                features
+                    .iter()
+                    .any(|f| (f.x as i64 - cx as i64).abs() <= 2 && (f.y as i64 - cy as i64).abs() <= 2),
+                "expected a corner near ({}, {}), found {:?}",
+                cx,
+                cy,
+                features
+            );
+        }
+    }
+}
@@ -6,12 +6,16 @@
 
 #[allow(clippy::collapsible_if)]
 pub mod fast;
+pub mod harris;
+pub mod pyramid;
 pub mod traits;
 pub mod utils;
 
-pub use fast::{FASTDetector, FASTDetectorParams};
+pub use fast::{FASTDetector, FASTDetectorParams, FASTPattern};
+pub use harris::{HarrisDetector, HarrisDetectorParams};
+pub use pyramid::{PyramidDetector, PyramidParams};
 pub use traits::KeypointDetector;
-pub use utils::ImgCoords;
+pub use utils::{Corner, ImgCoords};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,289 @@
+//! Scale-space wrapper that runs an underlying [`KeypointDetector`] over a pyramid of
+//! progressively downscaled copies of the input image, giving scale-invariant keypoints suitable
+//! for matching across zoom changes
+
+use image::{GrayImage, Luma};
+use std::cmp;
+use std::vec::Vec;
+
+use crate::traits::KeypointDetector;
+use crate::utils::Corner;
+
+/// Runs an underlying [`KeypointDetector`] over a fixed number of octaves of the input image -
+/// the original resolution plus `num_octaves - 1` copies downscaled by `scale_factor` each time -
+/// and maps every detected corner back to the coordinates of the original, full resolution
+/// image, tagging it with the octave/scale it was found at
+///
+/// Fewer octaves are run if a level would be smaller than the underlying detector's
+/// [`KeypointDetector::min_image_size`] along either axis: the pyramid stops before that level.
+///
+/// Generic over `D` so it works for both [`crate::fast::FASTDetector`] and
+/// [`crate::harris::HarrisDetector`].
+#[derive(Debug)]
+pub struct PyramidDetector<D> {
+    pub detector: D,
+    pub params: PyramidParams,
+}
+
+impl<D: KeypointDetector<ImageView = GrayImage>> KeypointDetector for PyramidDetector<D> {
+    type Params = PyramidParams;
+    type ImageView = GrayImage;
+
+    fn new() -> Self {
+        Self {
+            detector: D::new(),
+            params: PyramidParams::default(),
+        }
+    }
+    fn get_params(&self) -> &Self::Params {
+        &self.params
+    }
+
+    fn detect(&self, img: &Self::ImageView, features: &mut Vec<Corner>) {
+        self.run_octaves(img, None, features);
+    }
+
+    fn detect_in_mask(
+        &self,
+        img: &Self::ImageView,
+        mask: Option<&Self::ImageView>,
+        features: &mut Vec<Corner>,
+    ) {
+        if let Some(mask) = mask {
+            assert_eq!(
+                (mask.width(), mask.height()),
+                (img.width(), img.height()),
+                "mask dimensions must match the image being detected in"
+            );
+        }
+
+        self.run_octaves(img, mask, features);
+    }
+}
+
+impl<D: KeypointDetector<ImageView = GrayImage>> PyramidDetector<D> {
+    /// Shared by [`KeypointDetector::detect`]/[`KeypointDetector::detect_in_mask`]: walk the
+    /// octaves, downscaling `mask` alongside the image and forwarding both to the inner
+    /// detector's [`KeypointDetector::detect_in_mask`] so detectors that support masking (like
+    /// [`crate::fast::FASTDetector`]) apply it at every scale instead of just the original
+    /// resolution.
+    fn run_octaves(&self, img: &GrayImage, mask: Option<&GrayImage>, features: &mut Vec<Corner>) {
+        let min_size = self.detector.min_image_size();
+
+        let mut level = img.clone();
+        let mut mask_level = mask.cloned();
+        let mut scale = 1.0f32;
+
+        for octave in 0..self.params.num_octaves {
+            if level.width() < min_size || level.height() < min_size {
+                break;
+            }
+
+            let mut level_features = Vec::new();
+            self.detector
+                .detect_in_mask(&level, mask_level.as_ref(), &mut level_features);
+
+            features.extend(level_features.into_iter().map(|corner| {
+                Corner::new_at_scale(
+                    (corner.x as f32 * scale).round() as u32,
+                    (corner.y as f32 * scale).round() as u32,
+                    corner.score,
+                    octave,
+                    scale,
+                )
+            }));
+
+            if octave + 1 == self.params.num_octaves {
+                break;
+            }
+
+            let next_w = (level.width() as f32 / self.params.scale_factor).round() as u32;
+            let next_h = (level.height() as f32 / self.params.scale_factor).round() as u32;
+            if next_w < min_size || next_h < min_size {
+                break;
+            }
+
+            mask_level = mask_level.map(|m| downscale_mask(&m, next_w, next_h));
+            level = downscale(&level, next_w, next_h);
+            scale *= self.params.scale_factor;
+        }
+    }
+}
+
+/// Downscale `img` to `new_w x new_h` via a separable box filter, resampling whichever dimension
+/// shrinks the most first so the second pass runs over fewer pixels
+fn downscale(img: &GrayImage, new_w: u32, new_h: u32) -> GrayImage {
+    let width_ratio = img.width() as f32 / new_w as f32;
+    let height_ratio = img.height() as f32 / new_h as f32;
+
+    if width_ratio >= height_ratio {
+        let narrowed = resize_width(img, new_w);
+        resize_height(&narrowed, new_h)
+    } else {
+        let shortened = resize_height(img, new_h);
+        resize_width(&shortened, new_w)
+    }
+}
+
+/// Box-average `img` down to `new_w` columns, one row at a time
+fn resize_width(img: &GrayImage, new_w: u32) -> GrayImage {
+    let (w, h) = (img.width(), img.height());
+    let scale = w as f32 / new_w as f32;
+
+    let mut out = GrayImage::new(new_w, h);
+    for y in 0..h {
+        for x in 0..new_w {
+            let lo = (x as f32 * scale) as u32;
+            let hi = cmp::min(w, cmp::max(lo + 1, ((x + 1) as f32 * scale).ceil() as u32));
+
+            let mut sum = 0u32;
+            for sx in lo..hi {
+                sum += u32::from(img.get_pixel(sx, y)[0]);
+            }
+            out.put_pixel(x, y, Luma([(sum / (hi - lo)) as u8]));
+        }
+    }
+    out
+}
+
+/// Box-average `img` down to `new_h` rows, one column at a time
+fn resize_height(img: &GrayImage, new_h: u32) -> GrayImage {
+    let (w, h) = (img.width(), img.height());
+    let scale = h as f32 / new_h as f32;
+
+    let mut out = GrayImage::new(w, new_h);
+    for y in 0..new_h {
+        let lo = (y as f32 * scale) as u32;
+        let hi = cmp::min(h, cmp::max(lo + 1, ((y + 1) as f32 * scale).ceil() as u32));
+
+        for x in 0..w {
+            let mut sum = 0u32;
+            for sy in lo..hi {
+                sum += u32::from(img.get_pixel(x, sy)[0]);
+            }
+            out.put_pixel(x, y, Luma([(sum / (hi - lo)) as u8]));
+        }
+    }
+    out
+}
+
+/// Downscale a binary mask to `new_w x new_h`, setting an output pixel if any corresponding
+/// source pixel is set. Unlike the image's box-average downscale, a set region must never be
+/// averaged away to zero just because it's a minority of its source window.
+fn downscale_mask(mask: &GrayImage, new_w: u32, new_h: u32) -> GrayImage {
+    let (w, h) = (mask.width(), mask.height());
+    let x_scale = w as f32 / new_w as f32;
+    let y_scale = h as f32 / new_h as f32;
+
+    let mut out = GrayImage::new(new_w, new_h);
+    for y in 0..new_h {
+        let y_lo = (y as f32 * y_scale) as u32;
+        let y_hi = cmp::min(h, cmp::max(y_lo + 1, ((y + 1) as f32 * y_scale).ceil() as u32));
+
+        for x in 0..new_w {
+            let x_lo = (x as f32 * x_scale) as u32;
+            let x_hi = cmp::min(w, cmp::max(x_lo + 1, ((x + 1) as f32 * x_scale).ceil() as u32));
+
+            let any_set = (y_lo..y_hi)
+                .any(|sy| (x_lo..x_hi).any(|sx| mask.get_pixel(sx, sy)[0] != 0));
+            out.put_pixel(x, y, Luma([if any_set { 255 } else { 0 }]));
+        }
+    }
+    out
+}
+
+// PyramidParams --------------------------------------------------------------------------------
+
+/// Parameters of the [`PyramidDetector`]
+#[derive(Debug)]
+pub struct PyramidParams {
+    /// Number of pyramid levels to run the underlying detector on, including the original
+    /// resolution
+    pub num_octaves: u32,
+    /// Downscale factor applied between consecutive octaves, e.g. `1.2` or `2.0`
+    pub scale_factor: f32,
+}
+
+impl Default for PyramidParams {
+    fn default() -> Self {
+        Self {
+            num_octaves: 4,
+            scale_factor: 1.2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fast::{FASTDetector, FASTDetectorParams};
+
+    /// A white square on a black background, large enough to survive a few octaves of
+    /// downscaling, with unambiguous corners at its four vertices.
+    fn square_image() -> GrayImage {
+        GrayImage::from_fn(80, 80, |x, y| {
+            if (20..60).contains(&x) && (20..60).contains(&y) {
+                Luma([255])
+            } else {
+                Luma([0])
+            }
+        })
+    }
+
+    /// An axis-aligned right-angle corner only covers ~11 of [`crate::fast::FASTPattern::Circle16`]'s
+    /// 16 points, short of the default `min_contig_neighbors` of 12 - use the other documented
+    /// FAST-9-16 pairing instead so the square's corners are actually detected.
+    fn fast9_pyramid() -> PyramidDetector<FASTDetector> {
+        PyramidDetector {
+            detector: FASTDetector {
+                params: FASTDetectorParams {
+                    min_contig_neighbors: 9,
+                    ..FASTDetectorParams::default()
+                },
+            },
+            params: PyramidParams::default(),
+        }
+    }
+
+    #[test]
+    fn multi_octave_detection_finds_corners_at_every_octave() {
+        let img = square_image();
+        let detector = fast9_pyramid();
+
+        let mut features = Vec::new();
+        detector.detect(&img, &mut features);
+
+        assert!(!features.is_empty(), "should find corners on a square across the pyramid");
+        assert!(
+            features.iter().any(|f| f.octave == 0),
+            "original resolution should contribute at least one corner"
+        );
+        assert!(
+            features.iter().any(|f| f.octave > 0),
+            "at least one coarser octave should also contribute a corner"
+        );
+    }
+
+    #[test]
+    fn masked_detection_only_returns_corners_inside_the_mask() {
+        let img = square_image();
+        let mut mask = GrayImage::new(80, 80);
+        for y in 0..40 {
+            for x in 0..40 {
+                mask.put_pixel(x, y, Luma([255]));
+            }
+        }
+
+        let detector = fast9_pyramid();
+        let mut features = Vec::new();
+        detector.detect_in_mask(&img, Some(&mask), &mut features);
+
+        assert!(!features.is_empty(), "should still find the top-left corner inside the mask");
+        assert!(
+            features.iter().all(|f| f.x < 50 && f.y < 50),
+            "masked detection should never return corners from the far (60, 60) square vertex, \
+             well outside the mask: {:?}",
+            features
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::vec::Vec;
 
-use crate::utils::ImgCoords;
+use crate::utils::Corner;
 
 pub trait KeypointDetector {
     type Params;
@@ -11,6 +11,33 @@ pub trait KeypointDetector {
     fn new() -> Self;
     fn get_params(&self) -> &Self::Params;
 
-    /// Detect features of interest in the given image [`img`].
-    fn detect(&self, img: &Self::ImageView, features: &mut Vec<ImgCoords>);
+    /// Smallest width/height `img` can be for [`Self::detect`]/[`Self::detect_in_mask`] to run
+    /// without reading outside the image (e.g. a detector that skips a fixed border margin needs
+    /// at least `2 * margin + 1` pixels along each axis). Callers that resize `img` down, like
+    /// [`crate::pyramid::PyramidDetector`], must stop shrinking before going below this. Defaults
+    /// to `1`, i.e. no particular minimum.
+    fn min_image_size(&self) -> u32 {
+        1
+    }
+
+    /// Detect features of interest in the given image [`img`], emitting each as a scored
+    /// [`Corner`].
+    fn detect(&self, img: &Self::ImageView, features: &mut Vec<Corner>);
+
+    /// Like [`Self::detect`], but only evaluates pixels where `mask` is set (non-zero), skipping
+    /// masked-out pixels before running the detector's tests. This avoids wasted work and
+    /// spurious features outside a region of interest, e.g. a quadrilateral already isolated by
+    /// an earlier calibration/tracking step.
+    ///
+    /// `mask` must have the same dimensions as `img`. Detectors that don't implement masking fall
+    /// back to plain [`Self::detect`] and ignore it.
+    fn detect_in_mask(
+        &self,
+        img: &Self::ImageView,
+        mask: Option<&Self::ImageView>,
+        features: &mut Vec<Corner>,
+    ) {
+        let _ = mask;
+        self.detect(img, features);
+    }
 }
@@ -0,0 +1,215 @@
+//! Small shared types and helpers used across the detector implementations
+
+use std::cmp;
+use std::collections::HashMap;
+
+/// Pixel coordinates within an image
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ImgCoords {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl ImgCoords {
+    pub fn new(x: u32, y: u32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A detected keypoint together with its response strength, so callers can rank, threshold, or
+/// cap the number of returned features (e.g. "keep the top-K strongest corners")
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Corner {
+    pub x: u32,
+    pub y: u32,
+    pub score: f32,
+    /// Pyramid octave the corner was detected at, `0` for the original resolution. See
+    /// [`crate::pyramid::PyramidDetector`].
+    pub octave: u32,
+    /// Scale factor between the octave the corner was detected at and the original image, `1.0`
+    /// for the original resolution
+    pub scale: f32,
+}
+
+impl Corner {
+    pub fn new(x: u32, y: u32, score: f32) -> Self {
+        Self {
+            x,
+            y,
+            score,
+            octave: 0,
+            scale: 1.0,
+        }
+    }
+
+    pub(crate) fn new_at_scale(x: u32, y: u32, score: f32, octave: u32, scale: f32) -> Self {
+        Self {
+            x,
+            y,
+            score,
+            octave,
+            scale,
+        }
+    }
+}
+
+// Non-maximum suppression ------------------------------------------------------------------------
+//
+// Shared by every [`crate::traits::KeypointDetector`] implementation so they stay interchangeable
+// for callers - see [`crate::fast::FASTDetector`] and [`crate::harris::HarrisDetector`].
+
+/// Suppress every corner that has a strictly stronger neighbor within `radius` pixels.
+///
+/// Corners are bucketed into a uniform grid of `radius`-sized cells so each corner only has to
+/// examine the handful of candidates in its own and the 8 adjacent cells, instead of every other
+/// feature in the image. Candidates are then visited in ascending index order, matching the
+/// original all-pairs scan this replaced pixel for pixel - so for `radius == 1` this produces the
+/// exact same surviving set, ties included. In particular, an `idx` that's already been marked
+/// removed still runs its own forward pass against higher indices rather than being skipped - the
+/// original scan had no "already removed" check on the outer index either, so a removed corner can
+/// still suppress a later, weaker one.
+pub(crate) fn suppress_non_max(features: &mut Vec<Corner>, radius: u32) {
+    let cell_size = cmp::max(radius, 1) as i64;
+    let cell_of = |v: u32| v as i64 / cell_size;
+
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (idx, feat) in features.iter().enumerate() {
+        grid.entry((cell_of(feat.x), cell_of(feat.y)))
+            .or_default()
+            .push(idx);
+    }
+
+    let mut suppressed = vec![false; features.len()];
+    let mut candidates: Vec<usize> = Vec::new();
+    for idx in 0..features.len() {
+        let feat = features[idx];
+        let (cx, cy) = (cell_of(feat.x), cell_of(feat.y));
+
+        candidates.clear();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if let Some(bucket) = grid.get(&(cx + dx, cy + dy)) {
+                    candidates.extend(bucket.iter().copied().filter(|&idx2| idx2 > idx));
+                }
+            }
+        }
+        candidates.sort_unstable();
+
+        for &idx2 in &candidates {
+            if suppressed[idx2] {
+                continue;
+            }
+            let feat2 = features[idx2];
+            if (feat2.x as i64 - feat.x as i64).abs() > radius as i64
+                || (feat2.y as i64 - feat.y as i64).abs() > radius as i64
+            {
+                continue;
+            }
+
+            // keep the pixel with the highest score - mark the other as removed
+            if feat.score <= feat2.score {
+                suppressed[idx] = true;
+                break;
+            }
+            suppressed[idx2] = true;
+        }
+    }
+
+    let mut i: usize = 0;
+    features.retain(|_| (!suppressed[i], i += 1).0);
+}
+
+/// Adaptive non-maximal suppression: grow the suppression radius until at most `target` corners
+/// survive, so the kept corners stay local maxima while spreading out more evenly. Falls back to
+/// keeping the `target` strongest corners if growing the radius stops helping.
+pub(crate) fn suppress_to_target(features: &mut Vec<Corner>, radius: u32, target: usize) {
+    let mut radius = radius;
+    while features.len() > target {
+        let before = features.len();
+        radius += cmp::max(radius, 1);
+        suppress_non_max(features, radius);
+        if features.len() == before {
+            break;
+        }
+    }
+
+    if features.len() > target {
+        features.sort_unstable_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        features.truncate(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The original, pre-grid all-pairs scan `suppress_non_max` replaced, kept here only as a
+    /// test oracle: for every `idx`, keep walking higher indices in ascending order and mark the
+    /// weaker one removed, with no check for whether `idx` itself has already been removed.
+    fn old_suppress(features: &[Corner]) -> Vec<bool> {
+        let mut indices_to_remove: Vec<usize> = vec![];
+        for (idx, feat) in features.iter().enumerate() {
+            for idx2 in idx + 1..features.len() {
+                if indices_to_remove.contains(&idx2) {
+                    continue;
+                }
+                let feat2 = &features[idx2];
+                if (feat2.x as i64 - feat.x as i64).abs() > 1
+                    || (feat2.y as i64 - feat.y as i64).abs() > 1
+                {
+                    continue;
+                }
+                if feat.score <= feat2.score {
+                    indices_to_remove.push(idx);
+                    break;
+                }
+                indices_to_remove.push(idx2);
+            }
+        }
+        let mut suppressed = vec![false; features.len()];
+        for i in indices_to_remove {
+            suppressed[i] = true;
+        }
+        suppressed
+    }
+
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    /// `suppress_non_max` with `radius == 1` must keep exactly the set of corners the original
+    /// all-pairs scan would have kept, including its tie-breaking and cascading-removal quirks -
+    /// fuzzed over many randomized, heavily-tied corner sets since the grid bucketing processes
+    /// candidates in a different order than the flat scan it replaced.
+    #[test]
+    fn matches_original_all_pairs_scan() {
+        let mut state = 88_172_645_463_325_252_u64;
+        for trial in 0..200 {
+            let n = 10 + trial % 60;
+            let features: Vec<Corner> = (0..n)
+                .map(|_| {
+                    Corner::new(
+                        (xorshift(&mut state) % 30) as u32,
+                        (xorshift(&mut state) % 30) as u32,
+                        (xorshift(&mut state) % 20) as f32,
+                    )
+                })
+                .collect();
+
+            let expected_suppressed = old_suppress(&features);
+            let mut actual = features.clone();
+            suppress_non_max(&mut actual, 1);
+
+            let expected: Vec<Corner> = features
+                .iter()
+                .zip(expected_suppressed.iter())
+                .filter(|(_, &suppressed)| !suppressed)
+                .map(|(feat, _)| *feat)
+                .collect();
+            assert_eq!(actual, expected, "trial {trial} (n={n}) diverged from the original scan");
+        }
+    }
+}